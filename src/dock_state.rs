@@ -0,0 +1,468 @@
+use egui::Rect;
+
+use crate::tree::{NodeIndex, Split, Tree};
+use crate::widgets::dock_area::allowed_splits::AllowedSplits;
+use crate::widgets::dock_area::focus::{self, Direction};
+use crate::widgets::dock_area::DragPayload;
+
+/// Owns a dock's [`Tree`] along with the focus state that isn't part of the layout itself.
+pub struct DockState<Tab> {
+    tree: Tree<Tab>,
+    focused_leaf: Option<NodeIndex>,
+    /// Leaves in most-recently-focused order, used to break ties in directional navigation.
+    focus_order: Vec<NodeIndex>,
+    /// The tab a drag started from, if one is in progress. Lives here rather than in egui's
+    /// typed temp memory since `NodeIndex` doesn't implement `Default`, which `remove_temp`
+    /// requires; `DockState` persists across frames the same way temp memory would.
+    drag_payload: Option<DragPayload>,
+}
+
+impl<Tab> DockState<Tab> {
+    /// Creates a dock state with a single leaf holding `tabs`, focused by default.
+    pub fn new(tabs: Vec<Tab>) -> Self {
+        let tree = Tree::new(tabs);
+        let root = tree.root();
+        Self {
+            tree,
+            focused_leaf: Some(root),
+            focus_order: vec![root],
+            drag_payload: None,
+        }
+    }
+
+    pub fn tree(&self) -> &Tree<Tab> {
+        &self.tree
+    }
+
+    pub(crate) fn tree_mut(&mut self) -> &mut Tree<Tab> {
+        &mut self.tree
+    }
+
+    /// The leaf currently holding keyboard/paste focus, if any.
+    pub fn focused_leaf(&self) -> Option<NodeIndex> {
+        self.focused_leaf
+    }
+
+    pub fn set_focused_leaf(&mut self, leaf: NodeIndex) {
+        self.focus_order.retain(|&n| n != leaf);
+        self.focus_order.insert(0, leaf);
+        self.focused_leaf = Some(leaf);
+    }
+
+    /// Records that a drag has started from `payload`'s leaf/tab, for `handle_drop` to pick up
+    /// once the pointer is released (possibly several frames later).
+    pub(crate) fn start_drag(&mut self, payload: DragPayload) {
+        self.drag_payload = Some(payload);
+    }
+
+    /// Takes the in-progress drag's payload, if any, clearing it so a stale drag can't be
+    /// replayed on a later drop.
+    pub(crate) fn take_drag_payload(&mut self) -> Option<DragPayload> {
+        self.drag_payload.take()
+    }
+
+    /// The in-progress drag's payload, if any, without consuming it like
+    /// [`take_drag_payload`](Self::take_drag_payload) does. Used to preview where a drop would
+    /// land while the pointer is still down.
+    pub(crate) fn peek_drag_payload(&self) -> Option<DragPayload> {
+        self.drag_payload
+    }
+
+    /// Removes `leaf`'s tab at `tab_index`, pruning `leaf` from the tree if that empties it.
+    /// Returns the removed tab alongside the remap pair [`Tree::prune_if_empty`] produced, if
+    /// it pruned anything, so callers holding another `NodeIndex` can check whether it aliased
+    /// the leaf that got collapsed into a new location.
+    pub(crate) fn remove_tab(
+        &mut self,
+        leaf: NodeIndex,
+        tab_index: usize,
+    ) -> Option<(Tab, Option<(NodeIndex, NodeIndex)>)> {
+        let tab = self.tree.remove_tab(leaf, tab_index)?;
+        let remap = self.tree.prune_if_empty(leaf);
+        if let Some((old_id, new_id)) = remap {
+            self.remap_leaf(old_id, new_id);
+            // `leaf` was actually pruned out of the tree here, not merely relieved of one tab
+            // among several; a leaf that still holds tabs must stay untouched in the MRU
+            // bookkeeping instead of being wrongly evicted from `focus_order`.
+            self.focus_order.retain(|&n| n != leaf);
+            if self.focused_leaf == Some(leaf) {
+                self.focused_leaf = None;
+            }
+            // `leaf` itself (as opposed to its sibling, handled by `remap_leaf` above) may have
+            // been pruned and its index later handed back out by `Tree::alloc` to an unrelated
+            // leaf; drop a stale drag referencing it rather than let a later drop yank a tab
+            // from whatever ends up at that index.
+            if self.drag_payload.map(|(source, _)| source) == Some(leaf) {
+                self.drag_payload = None;
+            }
+        }
+        Some((tab, remap))
+    }
+
+    /// Splits `leaf` along `split`, moves `tab` into the new sibling, and focuses it.
+    ///
+    /// `leaf`'s index becomes a branch as a result of the split (see [`Tree::split_leaf`]); any
+    /// bookkeeping that referred to it as the focused leaf is remapped to wherever its tabs
+    /// ended up.
+    pub(crate) fn split_leaf_with_tab(
+        &mut self,
+        leaf: NodeIndex,
+        split: Split,
+        fraction: f32,
+        tab: Tab,
+    ) -> NodeIndex {
+        let (old_leaf, new_leaf) = self.tree.split_leaf(leaf, split, fraction, tab);
+        self.remap_leaf(leaf, old_leaf);
+        self.set_focused_leaf(new_leaf);
+        new_leaf
+    }
+
+    /// Updates focus bookkeeping after a leaf's identity changed from `old_id` to `new_id`
+    /// (e.g. because it was just split).
+    fn remap_leaf(&mut self, old_id: NodeIndex, new_id: NodeIndex) {
+        if self.focused_leaf == Some(old_id) {
+            self.focused_leaf = Some(new_id);
+        }
+        for entry in &mut self.focus_order {
+            if *entry == old_id {
+                *entry = new_id;
+            }
+        }
+        if let Some((source, tab_index)) = self.drag_payload {
+            if source == old_id {
+                self.drag_payload = Some((new_id, tab_index));
+            }
+        }
+    }
+
+    /// Moves focus from the currently focused leaf to the nearest leaf in `dir`, based on the
+    /// rects they were laid out in during the last [`DockArea::show`](crate::DockArea::show).
+    /// Does nothing if there is no focused leaf, it hasn't been laid out yet, or no leaf lies
+    /// in `dir`.
+    pub fn focus_adjacent(&mut self, dir: Direction) {
+        let Some(focused) = self.focused_leaf else {
+            return;
+        };
+        let Some(focused_rect) = self.tree.rect_of(focused) else {
+            return;
+        };
+
+        let candidates = self.ordered_leaf_rects(focused);
+        if let Some(target) =
+            focus::nearest_leaf_in_direction(focused_rect, candidates.into_iter(), dir)
+        {
+            self.set_focused_leaf(target);
+        }
+    }
+
+    /// Relocates the focused leaf's active tab into the neighboring leaf in `dir`, or, if there
+    /// is no neighbor in that direction, splits the dock's outermost edge to make room for it.
+    /// `allowed` gates that edge-split fallback; the move is a no-op if it's disallowed and
+    /// there was no neighbor to move into, or if there's no focused leaf with a tab to move.
+    /// Like [`focus_adjacent`](Self::focus_adjacent), also does nothing if the focused leaf
+    /// hasn't been laid out yet, rather than assuming no neighbor exists.
+    ///
+    /// If this empties the focused leaf, it's pruned from the tree (collapsing its parent into
+    /// its sibling) rather than left behind as a dead, tab-less pane. The dock's only leaf is
+    /// never pruned, so moving its last tab to an edge leaves that leaf empty beside the new one.
+    pub fn move_focused_tab(&mut self, dir: Direction, allowed: AllowedSplits) {
+        let Some(focused) = self.focused_leaf else {
+            return;
+        };
+        let Some(active) = self.tree.active_tab_index(focused) else {
+            return;
+        };
+        let Some(focused_rect) = self.tree.rect_of(focused) else {
+            return;
+        };
+
+        let candidates = self.ordered_leaf_rects(focused);
+        if let Some(target) =
+            focus::nearest_leaf_in_direction(focused_rect, candidates.into_iter(), dir)
+        {
+            let Some((tab, remap)) = self.remove_tab(focused, active) else {
+                return;
+            };
+            let target = remap_alias(target, remap);
+            self.tree.push_to_leaf(target, tab);
+            self.set_focused_leaf(target);
+            return;
+        }
+
+        if !focus::edge_split_allowed(dir, allowed) {
+            return;
+        }
+
+        let Some((tab, _remap)) = self.remove_tab(focused, active) else {
+            return;
+        };
+        let split = focus::edge_split_for_direction(dir);
+        let root = self.tree.root();
+        self.split_leaf_with_tab(root, split, 0.5, tab);
+    }
+
+    /// Every other leaf's rect, ordered most-recently-focused first so that directional
+    /// searches tie-break in that order.
+    fn ordered_leaf_rects(&self, exclude: NodeIndex) -> Vec<(NodeIndex, Rect)> {
+        let mut rects: Vec<(NodeIndex, Rect)> = self
+            .tree
+            .leaf_rects()
+            .filter(|(id, _)| *id != exclude)
+            .collect();
+        rects.sort_by_key(|(id, _)| self.focus_rank(*id));
+        rects
+    }
+
+    fn focus_rank(&self, id: NodeIndex) -> usize {
+        self.focus_order
+            .iter()
+            .position(|&n| n == id)
+            .unwrap_or(self.focus_order.len())
+    }
+}
+
+/// Applies a `(old, new)` remap pair to `id` if it aliased `old`, otherwise returns `id`
+/// unchanged. Used to fix up a `NodeIndex` a caller captured before a prune that may have moved
+/// it.
+fn remap_alias(id: NodeIndex, remap: Option<(NodeIndex, NodeIndex)>) -> NodeIndex {
+    match remap {
+        Some((old_id, new_id)) if old_id == id => new_id,
+        _ => id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree::Split;
+
+    fn layout(dock_state: &mut DockState<&str>, root_rect: Rect) {
+        dock_state.tree_mut().relayout(root_rect);
+    }
+
+    #[test]
+    fn focus_adjacent_picks_nearest_leaf_in_direction() {
+        let mut dock_state = DockState::new(vec!["a"]);
+        let root = dock_state.tree().root();
+        let (left, right) = dock_state
+            .tree_mut()
+            .split_leaf(root, Split::Right, 0.5, "b");
+        layout(
+            &mut dock_state,
+            Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(100.0, 100.0)),
+        );
+
+        dock_state.set_focused_leaf(left);
+        dock_state.focus_adjacent(Direction::Right);
+
+        assert_eq!(dock_state.focused_leaf(), Some(right));
+    }
+
+    #[test]
+    fn focus_adjacent_picks_nearest_leaf_below() {
+        let mut dock_state = DockState::new(vec!["a"]);
+        let root = dock_state.tree().root();
+        let (top, bottom) = dock_state
+            .tree_mut()
+            .split_leaf(root, Split::Below, 0.5, "b");
+        layout(
+            &mut dock_state,
+            Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(100.0, 100.0)),
+        );
+
+        dock_state.set_focused_leaf(top);
+        dock_state.focus_adjacent(Direction::Down);
+
+        assert_eq!(dock_state.focused_leaf(), Some(bottom));
+
+        dock_state.focus_adjacent(Direction::Up);
+
+        assert_eq!(dock_state.focused_leaf(), Some(top));
+    }
+
+    #[test]
+    fn focus_adjacent_is_a_no_op_when_no_leaf_lies_in_direction() {
+        let mut dock_state = DockState::new(vec!["a"]);
+        let root = dock_state.tree().root();
+        let (left, _right) = dock_state
+            .tree_mut()
+            .split_leaf(root, Split::Right, 0.5, "b");
+        layout(
+            &mut dock_state,
+            Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(100.0, 100.0)),
+        );
+
+        dock_state.set_focused_leaf(left);
+        dock_state.focus_adjacent(Direction::Left);
+
+        assert_eq!(dock_state.focused_leaf(), Some(left));
+    }
+
+    #[test]
+    fn focus_adjacent_breaks_ties_by_most_recently_focused() {
+        // Split the right leaf in two, giving two candidates equidistant from (and equally
+        // offset above/below) the focused left leaf.
+        let mut dock_state = DockState::new(vec!["a"]);
+        let root = dock_state.tree().root();
+        let (left, right) = dock_state
+            .tree_mut()
+            .split_leaf(root, Split::Right, 0.5, "b");
+        let (right_top, _right_bottom) =
+            dock_state
+                .tree_mut()
+                .split_leaf(right, Split::Below, 0.5, "c");
+        layout(
+            &mut dock_state,
+            Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(100.0, 100.0)),
+        );
+
+        dock_state.set_focused_leaf(right_top);
+        dock_state.set_focused_leaf(left);
+        dock_state.focus_adjacent(Direction::Right);
+
+        assert_eq!(dock_state.focused_leaf(), Some(right_top));
+    }
+
+    #[test]
+    fn move_focused_tab_relocates_into_an_existing_neighbor_and_prunes_the_empty_leaf() {
+        let mut dock_state = DockState::new(vec!["a"]);
+        let root = dock_state.tree().root();
+        let (left, _right) = dock_state
+            .tree_mut()
+            .split_leaf(root, Split::Right, 0.5, "b");
+        layout(
+            &mut dock_state,
+            Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(100.0, 100.0)),
+        );
+
+        dock_state.set_focused_leaf(left);
+        dock_state.move_focused_tab(Direction::Right, AllowedSplits::ALL);
+
+        // `left` is now empty and should have been pruned, collapsing the tree back down to a
+        // single leaf holding both tabs, instead of lingering as a dead, tab-less pane.
+        let merged = dock_state.focused_leaf().unwrap();
+        assert_eq!(dock_state.tree().leaf_rects().count(), 1);
+        assert_eq!(dock_state.tree().tab(merged, 0), Some(&"b"));
+        assert_eq!(dock_state.tree().tab(merged, 1), Some(&"a"));
+    }
+
+    #[test]
+    fn move_focused_tab_leaves_a_surviving_multi_tab_leaf_in_the_mru_bookkeeping() {
+        // `left` holds two tabs; moving one of them out into `right` still leaves `left` behind
+        // in the tree holding the other, so it must stay in `focus_order` instead of being
+        // evicted as though it had been pruned.
+        let mut dock_state = DockState::new(vec!["a", "x"]);
+        let root = dock_state.tree().root();
+        let (left, right) = dock_state
+            .tree_mut()
+            .split_leaf(root, Split::Right, 0.5, "b");
+        layout(
+            &mut dock_state,
+            Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(100.0, 100.0)),
+        );
+
+        dock_state.set_focused_leaf(left);
+        dock_state.move_focused_tab(Direction::Right, AllowedSplits::ALL);
+
+        assert!(dock_state.focus_order.contains(&left));
+        assert_eq!(dock_state.tree().tab(left, 0), Some(&"x"));
+        assert_eq!(dock_state.tree().tab(right, 0), Some(&"b"));
+        assert_eq!(dock_state.tree().tab(right, 1), Some(&"a"));
+    }
+
+    #[test]
+    fn move_focused_tab_splits_the_outer_edge_when_no_neighbor_exists() {
+        let mut dock_state = DockState::new(vec!["a"]);
+        let root = dock_state.tree().root();
+        dock_state.set_focused_leaf(root);
+        layout(
+            &mut dock_state,
+            Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(100.0, 100.0)),
+        );
+
+        dock_state.move_focused_tab(Direction::Right, AllowedSplits::ALL);
+
+        let new_leaf = dock_state.focused_leaf().unwrap();
+        assert_eq!(dock_state.tree().tab(new_leaf, 0), Some(&"a"));
+    }
+
+    #[test]
+    fn move_focused_tab_is_a_no_op_when_the_focused_leaf_has_not_been_laid_out() {
+        // A real neighbor exists to the right, but since nothing has been laid out yet this
+        // must not fall through to the edge-split fallback as if no neighbor existed.
+        let mut dock_state = DockState::new(vec!["a"]);
+        let root = dock_state.tree().root();
+        let (left, _right) = dock_state
+            .tree_mut()
+            .split_leaf(root, Split::Right, 0.5, "b");
+        dock_state.set_focused_leaf(left);
+
+        dock_state.move_focused_tab(Direction::Right, AllowedSplits::ALL);
+
+        assert_eq!(dock_state.focused_leaf(), Some(left));
+        assert_eq!(dock_state.tree().tab(left, 0), Some(&"a"));
+    }
+
+    #[test]
+    fn move_focused_tab_is_a_no_op_when_the_edge_split_is_disallowed() {
+        let mut dock_state = DockState::new(vec!["a"]);
+        let root = dock_state.tree().root();
+        dock_state.set_focused_leaf(root);
+        layout(
+            &mut dock_state,
+            Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(100.0, 100.0)),
+        );
+
+        dock_state.move_focused_tab(Direction::Right, AllowedSplits::TOP_BOTTOM);
+
+        assert_eq!(dock_state.focused_leaf(), Some(root));
+        assert_eq!(dock_state.tree().tab(root, 0), Some(&"a"));
+    }
+
+    #[test]
+    fn remove_tab_clears_a_cross_frame_drag_payload_that_referenced_the_pruned_leaf() {
+        let mut dock_state = DockState::new(vec!["a"]);
+        let root = dock_state.tree().root();
+        let (left, _right) = dock_state
+            .tree_mut()
+            .split_leaf(root, Split::Right, 0.5, "b");
+
+        dock_state.start_drag((left, 0));
+        // Empties and prunes `left`; its index may later be handed back out by `Tree::alloc` to
+        // an unrelated leaf, so any drag referencing it must not survive.
+        dock_state.remove_tab(left, 0);
+
+        assert_eq!(dock_state.take_drag_payload(), None);
+    }
+
+    #[test]
+    fn remove_tab_remaps_a_cross_frame_drag_payload_that_referenced_the_pruned_sibling() {
+        let mut dock_state = DockState::new(vec!["a"]);
+        let root = dock_state.tree().root();
+        let (_left, right) = dock_state
+            .tree_mut()
+            .split_leaf(root, Split::Right, 0.5, "b");
+        let (right_top, right_bottom) =
+            dock_state
+                .tree_mut()
+                .split_leaf(right, Split::Below, 0.5, "c");
+
+        dock_state.start_drag((right_bottom, 0));
+        // Empties and prunes `right_top`, collapsing `right_bottom` into `right`'s old index.
+        dock_state.remove_tab(right_top, 0);
+
+        assert_eq!(dock_state.take_drag_payload(), Some((right, 0)));
+    }
+
+    #[test]
+    fn split_leaf_with_tab_remaps_focus_to_the_moved_leaf() {
+        let mut dock_state = DockState::new(vec!["a"]);
+        let root = dock_state.tree().root();
+        dock_state.set_focused_leaf(root);
+
+        let new_leaf = dock_state.split_leaf_with_tab(root, Split::Right, 0.5, "b");
+
+        assert_eq!(dock_state.focused_leaf(), Some(new_leaf));
+        assert_eq!(dock_state.tree().tab(new_leaf, 0), Some(&"b"));
+    }
+}
@@ -0,0 +1,11 @@
+pub mod dock_state;
+pub mod tab_viewer;
+pub mod tree;
+pub mod widgets;
+
+pub use dock_state::DockState;
+pub use tab_viewer::TabViewer;
+pub use widgets::dock_area::allowed_splits::AllowedSplits;
+pub use widgets::dock_area::focus::Direction;
+pub use widgets::dock_area::split_policy::SplitPolicy;
+pub use widgets::dock_area::DockArea;
@@ -0,0 +1,20 @@
+use crate::widgets::dock_area::allowed_splits::AllowedSplits;
+
+/// Defines how tabs of type `Tab` are shown and behave within a [`DockArea`](crate::DockArea).
+pub trait TabViewer {
+    type Tab;
+
+    /// The title shown on `tab`'s label.
+    fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText;
+
+    /// Renders the content of `tab`.
+    fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab);
+
+    /// Which directions `tab` may be split in, intersected with the [`DockArea`](crate::DockArea)-wide
+    /// [`AllowedSplits`] before being applied to a drop. Override to forbid splitting a
+    /// particular tab in directions the dock otherwise allows, e.g. a fixed toolbar tab that
+    /// may only ever be split horizontally.
+    fn allowed_splits(&mut self, _tab: &Self::Tab) -> AllowedSplits {
+        AllowedSplits::ALL
+    }
+}
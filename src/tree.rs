@@ -0,0 +1,386 @@
+use egui::Rect;
+
+/// Which side of a node a new split creates, and the axis it splits along.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Split {
+    Left,
+    Right,
+    Above,
+    Below,
+}
+
+/// The index of a node within a [`Tree`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct NodeIndex(pub(crate) usize);
+
+pub(crate) enum Node<Tab> {
+    Leaf {
+        rect: Option<Rect>,
+        tabs: Vec<Tab>,
+        active: usize,
+    },
+    Branch {
+        rect: Option<Rect>,
+        split: Split,
+        fraction: f32,
+        children: [NodeIndex; 2],
+    },
+}
+
+impl<Tab> Node<Tab> {
+    fn rect(&self) -> Option<Rect> {
+        match self {
+            Node::Leaf { rect, .. } | Node::Branch { rect, .. } => *rect,
+        }
+    }
+
+    fn set_rect(&mut self, new_rect: Rect) {
+        match self {
+            Node::Leaf { rect, .. } | Node::Branch { rect, .. } => *rect = Some(new_rect),
+        }
+    }
+
+    fn set_rect_to_none(&mut self) {
+        match self {
+            Node::Leaf { rect, .. } | Node::Branch { rect, .. } => *rect = None,
+        }
+    }
+}
+
+fn empty_leaf<Tab>() -> Node<Tab> {
+    Node::Leaf {
+        rect: None,
+        tabs: Vec::new(),
+        active: 0,
+    }
+}
+
+/// A binary tree of dock leaves (each holding a stack of tabs) and the splits between them.
+pub struct Tree<Tab> {
+    nodes: Vec<Node<Tab>>,
+    root: NodeIndex,
+    /// Indices of nodes that [`prune_if_empty`](Self::prune_if_empty) abandoned and
+    /// [`alloc`](Self::alloc) may hand back out, so a long-running split/merge cycle doesn't
+    /// grow `nodes` without bound.
+    free: Vec<usize>,
+}
+
+impl<Tab> Tree<Tab> {
+    /// Creates a tree with a single leaf holding `tabs`.
+    pub fn new(tabs: Vec<Tab>) -> Self {
+        Self {
+            nodes: vec![Node::Leaf {
+                rect: None,
+                tabs,
+                active: 0,
+            }],
+            root: NodeIndex(0),
+            free: Vec::new(),
+        }
+    }
+
+    pub(crate) fn root(&self) -> NodeIndex {
+        self.root
+    }
+
+    pub(crate) fn rect_of(&self, node: NodeIndex) -> Option<Rect> {
+        self.nodes[node.0].rect()
+    }
+
+    /// The index of `leaf`'s currently active tab, or `None` if it has no tabs (or isn't a
+    /// leaf).
+    pub(crate) fn active_tab_index(&self, leaf: NodeIndex) -> Option<usize> {
+        match &self.nodes[leaf.0] {
+            Node::Leaf { tabs, active, .. } if !tabs.is_empty() => Some(*active),
+            _ => None,
+        }
+    }
+
+    /// Every leaf's index and laid-out rect. Leaves not yet laid out by [`relayout`](Self::relayout)
+    /// are skipped.
+    pub(crate) fn leaf_rects(&self) -> impl Iterator<Item = (NodeIndex, Rect)> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, node)| match node {
+                Node::Leaf {
+                    rect: Some(rect), ..
+                } => Some((NodeIndex(i), *rect)),
+                _ => None,
+            })
+    }
+
+    /// Recomputes every node's rect from `root_rect`, splitting each branch by its `fraction`.
+    pub(crate) fn relayout(&mut self, root_rect: Rect) {
+        self.relayout_node(self.root, root_rect);
+    }
+
+    fn relayout_node(&mut self, node: NodeIndex, rect: Rect) {
+        self.nodes[node.0].set_rect(rect);
+        let branch = match &self.nodes[node.0] {
+            Node::Branch {
+                split,
+                fraction,
+                children,
+                ..
+            } => Some((*split, *fraction, *children)),
+            Node::Leaf { .. } => None,
+        };
+        if let Some((split, fraction, children)) = branch {
+            let (first_rect, second_rect) = split_rect(rect, split, fraction);
+            self.relayout_node(children[0], first_rect);
+            self.relayout_node(children[1], second_rect);
+        }
+    }
+
+    /// A reference to a single tab, for inspecting it without removing it from its leaf.
+    pub(crate) fn tab(&self, leaf: NodeIndex, index: usize) -> Option<&Tab> {
+        match &self.nodes[leaf.0] {
+            Node::Leaf { tabs, .. } => tabs.get(index),
+            Node::Branch { .. } => None,
+        }
+    }
+
+    /// Mutable access to `leaf`'s tabs and its active tab index, for rendering tab headers and
+    /// handling clicks that change which tab is active.
+    pub(crate) fn leaf_tabs_mut(&mut self, leaf: NodeIndex) -> Option<(&mut Vec<Tab>, &mut usize)> {
+        match &mut self.nodes[leaf.0] {
+            Node::Leaf { tabs, active, .. } => Some((tabs, active)),
+            Node::Branch { .. } => None,
+        }
+    }
+
+    /// Appends `tab` to `leaf`'s tabs and makes it active, for relocating a tab that was removed
+    /// from elsewhere rather than creating a new leaf for it.
+    pub(crate) fn push_to_leaf(&mut self, leaf: NodeIndex, tab: Tab) {
+        if let Node::Leaf { tabs, active, .. } = &mut self.nodes[leaf.0] {
+            tabs.push(tab);
+            *active = tabs.len() - 1;
+        }
+    }
+
+    /// Removes `leaf`'s tab at `tab_index` and returns it, or `None` if `leaf` isn't a leaf or
+    /// `tab_index` is out of range. Clamps the active tab index if the removed tab was the last
+    /// one. Does not reclaim `leaf` itself if this empties it; see
+    /// [`prune_if_empty`](Self::prune_if_empty).
+    pub(crate) fn remove_tab(&mut self, leaf: NodeIndex, tab_index: usize) -> Option<Tab> {
+        let Node::Leaf { tabs, active, .. } = &mut self.nodes[leaf.0] else {
+            return None;
+        };
+        if tab_index >= tabs.len() {
+            return None;
+        }
+        let tab = tabs.remove(tab_index);
+        if *active >= tabs.len() {
+            *active = tabs.len().saturating_sub(1);
+        }
+        Some(tab)
+    }
+
+    /// The parent branch of `node` and which child slot (0 or 1) `node` occupies in it, or
+    /// `None` if `node` is the root (which has no parent).
+    fn parent_of(&self, node: NodeIndex) -> Option<(NodeIndex, usize)> {
+        self.nodes.iter().enumerate().find_map(|(i, n)| match n {
+            Node::Branch { children, .. } if children[0] == node => Some((NodeIndex(i), 0)),
+            Node::Branch { children, .. } if children[1] == node => Some((NodeIndex(i), 1)),
+            _ => None,
+        })
+    }
+
+    /// Collapses `leaf`'s parent branch into `leaf`'s sibling if `leaf` is a leaf that has been
+    /// emptied of tabs (typically just after [`remove_tab`](Self::remove_tab)), so moving the
+    /// last tab out of a leaf doesn't leave a dead, tab-less pane behind. Does nothing, and
+    /// returns `None`, if `leaf` still has tabs or is the root (the tree always keeps at least
+    /// one leaf).
+    ///
+    /// The sibling's content moves into its parent's index, since the parent keeps its own
+    /// index (so nodes above it need no updates) while the sibling's and `leaf`'s own old slots
+    /// are returned to the free list for [`alloc`](Self::alloc) to reuse, instead of lingering
+    /// in `nodes` forever. Returns `Some((old_sibling_index, new_sibling_index))` so callers can
+    /// remap any bookkeeping that referred to the sibling by its old identity.
+    pub(crate) fn prune_if_empty(&mut self, leaf: NodeIndex) -> Option<(NodeIndex, NodeIndex)> {
+        match &self.nodes[leaf.0] {
+            Node::Leaf { tabs, .. } if tabs.is_empty() => {}
+            _ => return None,
+        }
+
+        let (parent, slot) = self.parent_of(leaf)?;
+        let sibling = match &self.nodes[parent.0] {
+            Node::Branch { children, .. } => children[1 - slot],
+            _ => unreachable!("parent_of only returns branch nodes"),
+        };
+
+        let sibling_node = std::mem::replace(&mut self.nodes[sibling.0], empty_leaf());
+        self.nodes[parent.0] = sibling_node;
+
+        // `leaf` itself is now unreachable from `root`; clear its rect so a stale `relayout`
+        // from before the prune doesn't leave it looking laid out to `leaf_rects`, then hand
+        // both abandoned slots back to `alloc` rather than leaving them dead forever.
+        self.nodes[leaf.0].set_rect_to_none();
+        self.free.push(leaf.0);
+        self.free.push(sibling.0);
+
+        Some((sibling, parent))
+    }
+
+    /// Stores `node` at a free slot if [`prune_if_empty`](Self::prune_if_empty) has abandoned
+    /// one, reusing it instead of growing `nodes` indefinitely; otherwise appends a new slot.
+    fn alloc(&mut self, node: Node<Tab>) -> NodeIndex {
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                NodeIndex(index)
+            }
+            None => {
+                let index = NodeIndex(self.nodes.len());
+                self.nodes.push(node);
+                index
+            }
+        }
+    }
+
+    /// Splits `leaf` along `split`, creating a new sibling leaf on the `split` side containing
+    /// only `new_tab`. `leaf`'s own index becomes the new branch node: its original tabs move to
+    /// a fresh leaf, whose index is returned first, alongside the new sibling's index second.
+    /// Callers holding a `NodeIndex` equal to `leaf` (e.g. a focused-leaf bookkeeping field)
+    /// must remap it to one of these.
+    pub(crate) fn split_leaf(
+        &mut self,
+        leaf: NodeIndex,
+        split: Split,
+        fraction: f32,
+        new_tab: Tab,
+    ) -> (NodeIndex, NodeIndex) {
+        let old_leaf = std::mem::replace(
+            &mut self.nodes[leaf.0],
+            Node::Branch {
+                rect: None,
+                split,
+                fraction,
+                children: [NodeIndex(0), NodeIndex(0)],
+            },
+        );
+
+        let old_leaf_index = self.alloc(old_leaf);
+        let new_leaf_index = self.alloc(Node::Leaf {
+            rect: None,
+            tabs: vec![new_tab],
+            active: 0,
+        });
+
+        let children = match split {
+            Split::Left | Split::Above => [new_leaf_index, old_leaf_index],
+            Split::Right | Split::Below => [old_leaf_index, new_leaf_index],
+        };
+
+        if let Node::Branch { children: c, .. } = &mut self.nodes[leaf.0] {
+            *c = children;
+        }
+
+        (old_leaf_index, new_leaf_index)
+    }
+}
+
+fn split_rect(rect: Rect, split: Split, fraction: f32) -> (Rect, Rect) {
+    match split {
+        Split::Left | Split::Right => {
+            let split_x = rect.min.x + rect.width() * fraction;
+            let first = Rect::from_min_max(rect.min, egui::pos2(split_x, rect.max.y));
+            let second = Rect::from_min_max(egui::pos2(split_x, rect.min.y), rect.max);
+            (first, second)
+        }
+        Split::Above | Split::Below => {
+            let split_y = rect.min.y + rect.height() * fraction;
+            let first = Rect::from_min_max(rect.min, egui::pos2(rect.max.x, split_y));
+            let second = Rect::from_min_max(egui::pos2(rect.min.x, split_y), rect.max);
+            (first, second)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x0: f32, y0: f32, x1: f32, y1: f32) -> Rect {
+        Rect::from_min_max(egui::pos2(x0, y0), egui::pos2(x1, y1))
+    }
+
+    #[test]
+    fn relayout_splits_left_right_by_fraction() {
+        let mut tree = Tree::new(vec!["a"]);
+        let root = tree.root();
+        let (left, right) = tree.split_leaf(root, Split::Right, 0.5, "b");
+
+        tree.relayout(rect(0.0, 0.0, 100.0, 100.0));
+
+        let left_rect = tree.rect_of(left).unwrap();
+        let right_rect = tree.rect_of(right).unwrap();
+        assert_eq!(left_rect, rect(0.0, 0.0, 50.0, 100.0));
+        assert_eq!(right_rect, rect(50.0, 0.0, 100.0, 100.0));
+    }
+
+    #[test]
+    fn remove_tab_clamps_active_index() {
+        let mut tree = Tree::new(vec!["a", "b"]);
+        let root = tree.root();
+        assert_eq!(tree.active_tab_index(root), Some(0));
+        *tree.leaf_tabs_mut(root).unwrap().1 = 1;
+
+        let removed = tree.remove_tab(root, 1);
+        assert_eq!(removed, Some("b"));
+        assert_eq!(tree.active_tab_index(root), Some(0));
+    }
+
+    #[test]
+    fn prune_if_empty_collapses_parent_into_sibling() {
+        let mut tree = Tree::new(vec!["a"]);
+        let root = tree.root();
+        let (left, right) = tree.split_leaf(root, Split::Right, 0.5, "b");
+        tree.remove_tab(left, 0);
+
+        let (old_sibling, new_sibling) = tree.prune_if_empty(left).unwrap();
+        assert_eq!(old_sibling, right);
+
+        assert_eq!(tree.tab(new_sibling, 0), Some(&"b"));
+    }
+
+    #[test]
+    fn prune_if_empty_is_a_no_op_when_the_leaf_still_has_tabs() {
+        let mut tree = Tree::new(vec!["a"]);
+        let root = tree.root();
+        let (left, _right) = tree.split_leaf(root, Split::Right, 0.5, "b");
+
+        assert_eq!(tree.prune_if_empty(left), None);
+    }
+
+    #[test]
+    fn prune_if_empty_is_a_no_op_for_an_empty_root() {
+        let mut tree: Tree<&str> = Tree::new(vec![]);
+        let root = tree.root();
+
+        assert_eq!(tree.prune_if_empty(root), None);
+    }
+
+    #[test]
+    fn repeated_split_and_prune_does_not_grow_the_arena_without_bound() {
+        // Regression test: `split_leaf` used to always push two fresh nodes and
+        // `prune_if_empty` never reclaimed the slots it abandoned, so a long-running
+        // split/merge cycle leaked a `Node` per iteration. With the free list in place, the
+        // arena should stabilize at a small constant size instead of growing every iteration.
+        let mut tree = Tree::new(vec!["a"]);
+        let root = tree.root();
+
+        for _ in 0..50 {
+            let (left, _right) = tree.split_leaf(root, Split::Right, 0.5, "b");
+            tree.remove_tab(left, 0);
+            tree.prune_if_empty(left);
+        }
+
+        assert!(
+            tree.nodes.len() <= 3,
+            "arena grew to {} nodes",
+            tree.nodes.len()
+        );
+    }
+}
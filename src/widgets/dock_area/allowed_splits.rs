@@ -117,6 +117,14 @@ impl AllowedSplits {
         self.0 == Self::ALL.0
     }
 
+    /// Combines this value (typically the `DockArea`-wide default) with the value a
+    /// `TabViewer` returns from its `allowed_splits` hook for the tab under the cursor, so a
+    /// tab can further restrict, but never widen, the directions it may be split in.
+    #[inline(always)]
+    pub(crate) fn intersect(self, tab_allowed: Self) -> Self {
+        self & tab_allowed
+    }
+
     #[inline(always)]
     pub(crate) const fn allowed(&self, tree_split: &crate::tree::Split) -> bool {
         match tree_split {
@@ -127,3 +135,28 @@ impl AllowedSplits {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intersect_can_only_narrow() {
+        assert_eq!(
+            AllowedSplits::ALL.intersect(AllowedSplits::LEFT_RIGHT),
+            AllowedSplits::LEFT_RIGHT
+        );
+        assert_eq!(
+            AllowedSplits::TOP.intersect(AllowedSplits::LEFT),
+            AllowedSplits::NONE
+        );
+    }
+
+    #[test]
+    fn intersect_with_all_is_a_no_op() {
+        assert_eq!(
+            AllowedSplits::TOP_BOTTOM.intersect(AllowedSplits::ALL),
+            AllowedSplits::TOP_BOTTOM
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use egui::{Pos2, Rect};
+
+use crate::tree::Split;
+
+use super::allowed_splits::AllowedSplits;
+
+/// Picks the edge-aim [`Split`] for a drop at `pos` within `rect`, restricted to the
+/// directions `allowed` permits: whichever allowed edge `pos` is closest to wins.
+///
+/// Returns `None` if `allowed` forbids every direction.
+pub(crate) fn resolve_manual_split(rect: Rect, pos: Pos2, allowed: AllowedSplits) -> Option<Split> {
+    let candidates = [
+        (Split::Left, allowed.left(), pos.x - rect.min.x),
+        (Split::Right, allowed.right(), rect.max.x - pos.x),
+        (Split::Above, allowed.top(), pos.y - rect.min.y),
+        (Split::Below, allowed.bottom(), rect.max.y - pos.y),
+    ];
+
+    candidates
+        .into_iter()
+        .filter(|(_, is_allowed, _)| *is_allowed)
+        .min_by(|a, b| a.2.total_cmp(&b.2))
+        .map(|(split, _, _)| split)
+}
+
+/// The half of `rect` a drop resolving to `split` would carve out for the dragged tab, for
+/// painting a preview overlay while the drag is still in progress.
+pub(crate) fn preview_rect_for_split(rect: Rect, split: Split) -> Rect {
+    match split {
+        Split::Left => Rect::from_min_max(rect.min, egui::pos2(rect.center().x, rect.max.y)),
+        Split::Right => Rect::from_min_max(egui::pos2(rect.center().x, rect.min.y), rect.max),
+        Split::Above => Rect::from_min_max(rect.min, egui::pos2(rect.max.x, rect.center().y)),
+        Split::Below => Rect::from_min_max(egui::pos2(rect.min.x, rect.center().y), rect.max),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect() -> Rect {
+        Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(100.0, 100.0))
+    }
+
+    #[test]
+    fn picks_the_nearest_allowed_edge() {
+        let split = resolve_manual_split(rect(), egui::pos2(5.0, 50.0), AllowedSplits::ALL);
+        assert_eq!(split, Some(Split::Left));
+    }
+
+    #[test]
+    fn skips_edges_the_tab_forbids() {
+        // Closest to the left edge, but the tab only allows vertical splits.
+        let split = resolve_manual_split(rect(), egui::pos2(5.0, 50.0), AllowedSplits::TOP_BOTTOM);
+        assert_eq!(split, Some(Split::Above));
+    }
+
+    #[test]
+    fn none_when_nothing_is_allowed() {
+        let split = resolve_manual_split(rect(), egui::pos2(5.0, 50.0), AllowedSplits::NONE);
+        assert_eq!(split, None);
+    }
+
+    #[test]
+    fn preview_rect_covers_the_half_the_split_would_carve_out() {
+        assert_eq!(
+            preview_rect_for_split(rect(), Split::Left),
+            Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(50.0, 100.0))
+        );
+        assert_eq!(
+            preview_rect_for_split(rect(), Split::Below),
+            Rect::from_min_max(egui::pos2(0.0, 50.0), egui::pos2(100.0, 100.0))
+        );
+    }
+}
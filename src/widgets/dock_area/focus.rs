@@ -0,0 +1,152 @@
+use egui::Rect;
+
+use crate::tree::Split;
+
+use super::allowed_splits::AllowedSplits;
+
+/// A cardinal direction used for keyboard-driven navigation between dock leaves.
+///
+/// Backs [`DockState::focus_adjacent`](crate::DockState::focus_adjacent), which moves focus
+/// to the nearest leaf in the given direction based on the rects they were laid out in during
+/// the last frame, and [`DockState::move_focused_tab`](crate::DockState::move_focused_tab),
+/// which relocates the focused tab into that neighbor instead.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl Direction {
+    /// Maps an arrow key to the [`Direction`] it represents, for `DockArea`'s directional
+    /// focus key bindings.
+    pub(crate) fn from_key(key: egui::Key) -> Option<Self> {
+        match key {
+            egui::Key::ArrowLeft => Some(Direction::Left),
+            egui::Key::ArrowRight => Some(Direction::Right),
+            egui::Key::ArrowUp => Some(Direction::Up),
+            egui::Key::ArrowDown => Some(Direction::Down),
+            _ => None,
+        }
+    }
+}
+
+/// Picks the best neighbor of `focused` among `candidates` when moving in `dir`.
+///
+/// Only candidates whose rect lies strictly in `dir` relative to `focused`'s center are
+/// considered (e.g. for [`Direction::Right`], `candidate.min.x >= focused.center().x`). Among
+/// those, the one minimizing `directional_gap + 2.0 * perpendicular_offset` wins, which favors
+/// leaves that are both close in the travel direction and well-aligned perpendicular to it.
+/// Ties are resolved in favor of whichever candidate appears first in `candidates`, so callers
+/// that want "most recently focused" tie-breaking should order `candidates` that way.
+pub(crate) fn nearest_leaf_in_direction<Id: Copy>(
+    focused: Rect,
+    candidates: impl Iterator<Item = (Id, Rect)>,
+    dir: Direction,
+) -> Option<Id> {
+    let mut best: Option<(Id, f32)> = None;
+    for (id, rect) in candidates {
+        let Some(score) = directional_score(focused, rect, dir) else {
+            continue;
+        };
+        let is_better = match best {
+            Some((_, best_score)) => score < best_score,
+            None => true,
+        };
+        if is_better {
+            best = Some((id, score));
+        }
+    }
+    best.map(|(id, _)| id)
+}
+
+/// The [`Split`] to perform against the dock's outer edge when `move_focused_tab` moves the
+/// focused tab in `dir` and no neighboring leaf exists in that direction to receive it.
+pub(crate) fn edge_split_for_direction(dir: Direction) -> Split {
+    match dir {
+        Direction::Left => Split::Left,
+        Direction::Right => Split::Right,
+        Direction::Up => Split::Above,
+        Direction::Down => Split::Below,
+    }
+}
+
+/// Whether `move_focused_tab`'s edge-split fallback for `dir` is permitted by `allowed`. The
+/// move is a no-op when this returns `false` and there was no neighboring leaf to move into.
+pub(crate) fn edge_split_allowed(dir: Direction, allowed: AllowedSplits) -> bool {
+    allowed.allowed(&edge_split_for_direction(dir))
+}
+
+/// Scores `candidate` as a neighbor of `focused` in direction `dir`, or returns `None` if
+/// `candidate` doesn't lie strictly in that direction.
+fn directional_score(focused: Rect, candidate: Rect, dir: Direction) -> Option<f32> {
+    let focused_center = focused.center();
+    let candidate_center = candidate.center();
+
+    let (directional_gap, perpendicular_offset) = match dir {
+        Direction::Right => {
+            if candidate.min.x < focused_center.x {
+                return None;
+            }
+            (
+                candidate.min.x - focused_center.x,
+                (focused_center.y - candidate_center.y).abs(),
+            )
+        }
+        Direction::Left => {
+            if candidate.max.x > focused_center.x {
+                return None;
+            }
+            (
+                focused_center.x - candidate.max.x,
+                (focused_center.y - candidate_center.y).abs(),
+            )
+        }
+        Direction::Down => {
+            if candidate.min.y < focused_center.y {
+                return None;
+            }
+            (
+                candidate.min.y - focused_center.y,
+                (focused_center.x - candidate_center.x).abs(),
+            )
+        }
+        Direction::Up => {
+            if candidate.max.y > focused_center.y {
+                return None;
+            }
+            (
+                focused_center.y - candidate.max.y,
+                (focused_center.x - candidate_center.x).abs(),
+            )
+        }
+    };
+
+    Some(directional_gap + 2.0 * perpendicular_offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x0: f32, y0: f32, x1: f32, y1: f32) -> Rect {
+        Rect::from_min_max(egui::pos2(x0, y0), egui::pos2(x1, y1))
+    }
+
+    #[test]
+    fn nearest_leaf_in_direction_finds_the_leaf_below_but_none_above() {
+        let focused = rect(0.0, 0.0, 50.0, 50.0);
+        let below = rect(0.0, 50.0, 50.0, 100.0);
+        let candidates = vec![(1, below)];
+
+        assert_eq!(
+            nearest_leaf_in_direction(focused, candidates.clone().into_iter(), Direction::Down),
+            Some(1)
+        );
+        assert_eq!(
+            nearest_leaf_in_direction(focused, candidates.into_iter(), Direction::Up),
+            None
+        );
+    }
+}
@@ -0,0 +1,358 @@
+use egui::{Context, Event, Modifiers};
+
+use crate::dock_state::DockState;
+use crate::tab_viewer::TabViewer;
+use crate::tree::{self, NodeIndex};
+
+pub mod allowed_splits;
+pub(crate) mod drop;
+pub mod focus;
+pub mod split_policy;
+
+use allowed_splits::AllowedSplits;
+use focus::Direction;
+use split_policy::SplitPolicy;
+
+/// Identifies the tab an in-progress drag started from, persisted on [`DockState`] across the
+/// frames a drag spans.
+pub(crate) type DragPayload = (NodeIndex, usize);
+
+/// Renders a [`DockState`]'s tabs and leaves, and handles the input (drag-and-drop, keyboard
+/// navigation) used to rearrange them.
+pub struct DockArea<'tree, Tab> {
+    dock_state: &'tree mut DockState<Tab>,
+    allowed_splits: AllowedSplits,
+    split_policy: SplitPolicy,
+}
+
+impl<'tree, Tab> DockArea<'tree, Tab> {
+    /// Creates a `DockArea` that renders and edits `dock_state`.
+    pub fn new(dock_state: &'tree mut DockState<Tab>) -> Self {
+        Self {
+            dock_state,
+            allowed_splits: AllowedSplits::ALL,
+            split_policy: SplitPolicy::default(),
+        }
+    }
+
+    /// Restricts which directions leaves in this dock may be split in. Further restricted
+    /// per-tab by [`TabViewer::allowed_splits`].
+    pub fn allowed_splits(mut self, allowed_splits: AllowedSplits) -> Self {
+        self.allowed_splits = allowed_splits;
+        self
+    }
+
+    /// Chooses how the split direction is picked when a tab is dropped onto a leaf. Defaults to
+    /// [`SplitPolicy::Manual`].
+    pub fn split_policy(mut self, split_policy: SplitPolicy) -> Self {
+        self.split_policy = split_policy;
+        self
+    }
+
+    /// Lays out `dock_state`'s tree against `ctx`'s screen rect, renders every tab via
+    /// `tab_viewer`, and applies this frame's input (keyboard navigation, drag-and-drop).
+    pub fn show<V>(mut self, ctx: &Context, tab_viewer: &mut V)
+    where
+        V: TabViewer<Tab = Tab>,
+    {
+        self.dock_state.tree_mut().relayout(ctx.screen_rect());
+        self.handle_focus_navigation_input(ctx, tab_viewer);
+
+        let leaves: Vec<(NodeIndex, egui::Rect)> = self.dock_state.tree().leaf_rects().collect();
+        egui::CentralPanel::default().show(ctx, |ui| {
+            for (leaf, rect) in leaves {
+                self.show_leaf(ui, tab_viewer, leaf, rect);
+            }
+        });
+
+        self.show_drop_preview(ctx, tab_viewer);
+        self.handle_drop(ctx, tab_viewer);
+    }
+
+    /// Moves focus to the nearest leaf in an arrow key's direction while Alt is held, or moves
+    /// the focused tab there instead when Shift is held too.
+    fn handle_focus_navigation_input<V>(&mut self, ctx: &Context, tab_viewer: &mut V)
+    where
+        V: TabViewer<Tab = Tab>,
+    {
+        let input = ctx.input(|input| {
+            input.events.iter().find_map(|event| match event {
+                Event::Key {
+                    key,
+                    pressed: true,
+                    modifiers:
+                        Modifiers {
+                            alt: true, shift, ..
+                        },
+                    ..
+                } => Direction::from_key(*key).map(|dir| (dir, *shift)),
+                _ => None,
+            })
+        });
+
+        let Some((dir, move_tab)) = input else {
+            return;
+        };
+
+        if move_tab {
+            let allowed = match self.dock_state.focused_leaf() {
+                Some(leaf) => self.allowed_splits_for(leaf, tab_viewer),
+                None => self.allowed_splits,
+            };
+            self.dock_state.move_focused_tab(dir, allowed);
+        } else {
+            self.dock_state.focus_adjacent(dir);
+        }
+    }
+
+    /// `self.allowed_splits`, narrowed by whatever [`TabViewer::allowed_splits`] returns for
+    /// `leaf`'s active tab. Shared by every site that resolves a split direction against a
+    /// leaf: `handle_drop`, the keyboard move-tab binding, and `show_drop_preview`.
+    fn allowed_splits_for<V>(&self, leaf: NodeIndex, tab_viewer: &mut V) -> AllowedSplits
+    where
+        V: TabViewer<Tab = Tab>,
+    {
+        let active_tab = self
+            .dock_state
+            .tree()
+            .active_tab_index(leaf)
+            .and_then(|index| self.dock_state.tree().tab(leaf, index));
+        match active_tab {
+            Some(tab) => self
+                .allowed_splits
+                .intersect(tab_viewer.allowed_splits(tab)),
+            None => self.allowed_splits,
+        }
+    }
+
+    fn show_leaf<V>(
+        &mut self,
+        ui: &mut egui::Ui,
+        tab_viewer: &mut V,
+        leaf: NodeIndex,
+        rect: egui::Rect,
+    ) where
+        V: TabViewer<Tab = Tab>,
+    {
+        ui.allocate_ui_at_rect(rect, |ui| {
+            let mut clicked = false;
+            let mut dragged = None;
+
+            {
+                let Some((tabs, active)) = self.dock_state.tree_mut().leaf_tabs_mut(leaf) else {
+                    return;
+                };
+
+                ui.horizontal(|ui| {
+                    for (index, tab) in tabs.iter_mut().enumerate() {
+                        let title = tab_viewer.title(tab);
+                        // `selectable_label` only allocates `Sense::click()`; re-interact with
+                        // the same rect/id under `click_and_drag()` so headers can start a drag.
+                        let label = ui.selectable_label(index == *active, title);
+                        let header =
+                            ui.interact(label.rect, label.id, egui::Sense::click_and_drag());
+                        if header.clicked() {
+                            *active = index;
+                            clicked = true;
+                        }
+                        if header.drag_started() {
+                            dragged = Some(index);
+                        }
+                    }
+                });
+
+                if let Some(tab) = tabs.get_mut(*active) {
+                    tab_viewer.ui(ui, tab);
+                }
+            }
+
+            if clicked {
+                self.dock_state.set_focused_leaf(leaf);
+            }
+            if let Some(index) = dragged {
+                self.dock_state.start_drag((leaf, index));
+            }
+        });
+    }
+
+    /// Resolves where a tab dragged from `source_leaf` would land if dropped at `drop_pos`
+    /// right now: the leaf under the pointer, its rect, and the [`Split`] that drop would
+    /// perform. Shared by `handle_drop` (to actually perform it) and `show_drop_preview` (to
+    /// paint it before the pointer is released).
+    fn resolve_drop<V>(
+        &mut self,
+        source_leaf: NodeIndex,
+        drop_pos: egui::Pos2,
+        tab_viewer: &mut V,
+    ) -> Option<(NodeIndex, egui::Rect, tree::Split)>
+    where
+        V: TabViewer<Tab = Tab>,
+    {
+        let (target_leaf, target_rect) = self
+            .dock_state
+            .tree()
+            .leaf_rects()
+            .find(|(leaf, rect)| *leaf != source_leaf && rect.contains(drop_pos))?;
+
+        // Gate the split by the *target* leaf's active tab, not the dragged one: it's whatever
+        // is already in the leaf being split (e.g. a fixed toolbar pane) that should restrict
+        // the directions it can be split in, regardless of which tab is being dropped onto it.
+        let allowed = self.allowed_splits_for(target_leaf, tab_viewer);
+        let auto = (self.split_policy == SplitPolicy::AutoSplit
+            && split_policy::is_in_center_region(target_rect, drop_pos))
+        .then(|| split_policy::auto_split(target_rect, drop_pos, allowed))
+        .flatten();
+        let split = auto.or_else(|| drop::resolve_manual_split(target_rect, drop_pos, allowed))?;
+
+        Some((target_leaf, target_rect, split))
+    }
+
+    /// While a drag is in progress and the pointer hasn't been released this frame, paints a
+    /// translucent overlay over the half of the hovered leaf the tab would land in if dropped
+    /// now, so the split preview only ever highlights a direction `resolve_drop` (and therefore
+    /// `handle_drop`) would actually honor.
+    fn show_drop_preview<V>(&mut self, ctx: &Context, tab_viewer: &mut V)
+    where
+        V: TabViewer<Tab = Tab>,
+    {
+        if ctx.input(|i| i.pointer.any_released()) {
+            return;
+        }
+        let Some((source_leaf, _tab_index)) = self.dock_state.peek_drag_payload() else {
+            return;
+        };
+        let Some(drop_pos) = ctx.pointer_interact_pos() else {
+            return;
+        };
+        let Some((_target_leaf, target_rect, split)) =
+            self.resolve_drop(source_leaf, drop_pos, tab_viewer)
+        else {
+            return;
+        };
+
+        let preview_rect = drop::preview_rect_for_split(target_rect, split);
+        ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            egui::Id::new("egui_dock::drop_preview"),
+        ))
+        .rect_filled(
+            preview_rect,
+            0.0,
+            egui::Color32::from_rgba_unmultiplied(100, 150, 255, 60),
+        );
+    }
+
+    fn handle_drop<V>(&mut self, ctx: &Context, tab_viewer: &mut V)
+    where
+        V: TabViewer<Tab = Tab>,
+    {
+        if !ctx.input(|i| i.pointer.any_released()) {
+            return;
+        }
+
+        let Some((source_leaf, tab_index)) = self.dock_state.take_drag_payload() else {
+            return;
+        };
+
+        let Some(drop_pos) = ctx.pointer_interact_pos() else {
+            return;
+        };
+
+        let Some((target_leaf, _target_rect, split)) =
+            self.resolve_drop(source_leaf, drop_pos, tab_viewer)
+        else {
+            return;
+        };
+
+        let Some((tab, remap)) = self.dock_state.remove_tab(source_leaf, tab_index) else {
+            return;
+        };
+        // `target_leaf` may have been the sibling `remove_tab` just pruned the (now empty)
+        // source leaf into, in which case it moved to a new index.
+        let target_leaf = match remap {
+            Some((old_id, new_id)) if old_id == target_leaf => new_id,
+            _ => target_leaf,
+        };
+        self.dock_state
+            .split_leaf_with_tab(target_leaf, split, 0.5, tab);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dock_state::DockState;
+    use crate::tree::Split;
+
+    /// A tab titled `"toolbar"` may only be split horizontally; every other tab allows any
+    /// direction. Mirrors the motivating example from the `allowed_splits` request: a fixed
+    /// toolbar tab that forbids being split vertically.
+    struct StubViewer;
+
+    impl TabViewer for StubViewer {
+        type Tab = &'static str;
+
+        fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText {
+            (*tab).into()
+        }
+
+        fn ui(&mut self, _ui: &mut egui::Ui, _tab: &mut Self::Tab) {}
+
+        fn allowed_splits(&mut self, tab: &Self::Tab) -> AllowedSplits {
+            if *tab == "toolbar" {
+                AllowedSplits::LEFT_RIGHT
+            } else {
+                AllowedSplits::ALL
+            }
+        }
+    }
+
+    /// Builds a two-leaf dock: `left` (`target_tab`, 0..50 wide) and `right` (`"other"`,
+    /// 50..100 wide), both spanning the full 100-tall rect.
+    fn two_leaf_dock(target_tab: &'static str) -> (DockState<&'static str>, NodeIndex, NodeIndex) {
+        let mut dock_state = DockState::new(vec![target_tab]);
+        let root = dock_state.tree().root();
+        let (left, right) = dock_state
+            .tree_mut()
+            .split_leaf(root, Split::Right, 0.5, "other");
+        dock_state.tree_mut().relayout(egui::Rect::from_min_max(
+            egui::pos2(0.0, 0.0),
+            egui::pos2(100.0, 100.0),
+        ));
+        (dock_state, left, right)
+    }
+
+    #[test]
+    fn resolve_drop_uses_the_dock_wide_default_when_the_tab_imposes_no_restriction() {
+        let (mut dock_state, left, right) = two_leaf_dock("panel");
+        let mut viewer = StubViewer;
+        let mut area = DockArea::new(&mut dock_state);
+
+        // Closest to `left`'s top edge; with no per-tab restriction this is a plain edge-aim
+        // pick of `Split::Above`.
+        let resolved = area.resolve_drop(right, egui::pos2(10.0, 2.0), &mut viewer);
+
+        assert_eq!(
+            resolved.map(|(leaf, _, split)| (leaf, split)),
+            Some((left, Split::Above))
+        );
+    }
+
+    #[test]
+    fn resolve_drop_narrows_to_the_target_tabs_allowed_splits() {
+        let (mut dock_state, left, right) = two_leaf_dock("toolbar");
+        let mut viewer = StubViewer;
+        let mut area = DockArea::new(&mut dock_state);
+
+        // Same drop position as above -- still closest to `left`'s top edge -- but `left` now
+        // holds the toolbar tab, whose `allowed_splits` forbids vertical splits. The dock-wide
+        // `AllowedSplits::ALL` default must be narrowed to `LEFT_RIGHT`, so the nearest
+        // *allowed* edge (`Left`) wins instead of the nearer but forbidden `Above`.
+        let resolved = area.resolve_drop(right, egui::pos2(10.0, 2.0), &mut viewer);
+
+        assert_eq!(
+            resolved.map(|(leaf, _, split)| (leaf, split)),
+            Some((left, Split::Left))
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use egui::{Pos2, Rect};
+
+use crate::tree::Split;
+
+use super::allowed_splits::AllowedSplits;
+
+/// How the direction of a new split is chosen when a tab is dropped into a leaf.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub enum SplitPolicy {
+    /// The allowed edge of the leaf nearest the drop point determines the [`Split`] direction,
+    /// wherever in the leaf the tab is dropped.
+    #[default]
+    Manual,
+    /// Dropping into the center region of a leaf picks the split direction from the leaf's
+    /// geometry instead: wider-than-tall leaves split [`Left`](Split::Left)/[`Right`](Split::Right),
+    /// taller-than-wide leaves split [`Above`](Split::Above)/[`Below`](Split::Below). Which
+    /// half of the rect the pointer is in picks between the two.
+    AutoSplit,
+}
+
+/// Whether `pos` falls in the center region of `rect` (the inner half along each axis), where
+/// [`SplitPolicy::AutoSplit`] applies instead of the manual edge-aim split.
+pub(crate) fn is_in_center_region(rect: Rect, pos: Pos2) -> bool {
+    rect.shrink2(egui::vec2(rect.width() * 0.25, rect.height() * 0.25))
+        .contains(pos)
+}
+
+/// Decides the [`Split`] for a center-region drop under [`SplitPolicy::AutoSplit`], given the
+/// target leaf's `rect` and the pointer's `drop_pos`.
+///
+/// Only meaningful when `allowed` permits splitting along both axes; callers should fall back
+/// to the manual edge-aim behavior otherwise, so this returns `None` in that case. Also returns
+/// `None` if the specific direction the geometry picks isn't itself allowed (e.g. `LEFT | BOTTOM`
+/// passes the axis check above but still can't produce `Split::Right`).
+pub(crate) fn auto_split(rect: Rect, drop_pos: Pos2, allowed: AllowedSplits) -> Option<Split> {
+    if !allowed.left_or_right() || !allowed.top_or_bottom() {
+        return None;
+    }
+
+    let split = if rect.width() > rect.height() {
+        if drop_pos.x < rect.center().x {
+            Split::Left
+        } else {
+            Split::Right
+        }
+    } else if drop_pos.y < rect.center().y {
+        Split::Above
+    } else {
+        Split::Below
+    };
+
+    allowed.allowed(&split).then_some(split)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wide_rect() -> Rect {
+        Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(200.0, 100.0))
+    }
+
+    fn tall_rect() -> Rect {
+        Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(100.0, 200.0))
+    }
+
+    #[test]
+    fn wider_than_tall_splits_left_or_right() {
+        let split = auto_split(wide_rect(), egui::pos2(40.0, 50.0), AllowedSplits::ALL);
+        assert_eq!(split, Some(Split::Left));
+
+        let split = auto_split(wide_rect(), egui::pos2(160.0, 50.0), AllowedSplits::ALL);
+        assert_eq!(split, Some(Split::Right));
+    }
+
+    #[test]
+    fn taller_than_wide_splits_above_or_below() {
+        let split = auto_split(tall_rect(), egui::pos2(50.0, 40.0), AllowedSplits::ALL);
+        assert_eq!(split, Some(Split::Above));
+
+        let split = auto_split(tall_rect(), egui::pos2(50.0, 160.0), AllowedSplits::ALL);
+        assert_eq!(split, Some(Split::Below));
+    }
+
+    #[test]
+    fn none_unless_both_axes_are_allowed() {
+        let split = auto_split(
+            wide_rect(),
+            egui::pos2(40.0, 50.0),
+            AllowedSplits::LEFT_RIGHT,
+        );
+        assert_eq!(split, None);
+    }
+
+    #[test]
+    fn none_when_the_resolved_direction_is_forbidden() {
+        // Both axes have an allowed direction, but not the one the geometry resolves to: a wide
+        // rect with the pointer on the right half wants `Split::Right`, which `LEFT | BOTTOM`
+        // doesn't permit.
+        let allowed = AllowedSplits::LEFT | AllowedSplits::BOTTOM;
+        let split = auto_split(wide_rect(), egui::pos2(160.0, 50.0), allowed);
+        assert_eq!(split, None);
+    }
+
+    #[test]
+    fn center_region_excludes_the_outer_quarter() {
+        let rect = wide_rect();
+        assert!(is_in_center_region(rect, rect.center()));
+        assert!(!is_in_center_region(rect, rect.min));
+    }
+}
@@ -0,0 +1 @@
+pub mod dock_area;
@@ -0,0 +1,31 @@
+//! Exercises the public API surface from outside the crate, so a `pub(crate)` module that
+//! shadows a `pub` item's argument type fails the build here instead of going unnoticed by the
+//! in-crate unit tests.
+
+use egui_dock::{AllowedSplits, Direction, DockState, SplitPolicy};
+
+#[test]
+fn focus_adjacent_is_callable_from_outside_the_crate() {
+    let mut dock_state = DockState::new(vec!["a"]);
+    let only_leaf = dock_state.focused_leaf();
+
+    dock_state.focus_adjacent(Direction::Right);
+
+    assert_eq!(dock_state.focused_leaf(), only_leaf);
+}
+
+#[test]
+fn split_policy_is_nameable_from_outside_the_crate() {
+    assert_eq!(SplitPolicy::default(), SplitPolicy::Manual);
+    assert_ne!(SplitPolicy::AutoSplit, SplitPolicy::Manual);
+}
+
+#[test]
+fn move_focused_tab_is_callable_from_outside_the_crate() {
+    let mut dock_state = DockState::new(vec!["a"]);
+    let only_leaf = dock_state.focused_leaf();
+
+    dock_state.move_focused_tab(Direction::Right, AllowedSplits::NONE);
+
+    assert_eq!(dock_state.focused_leaf(), only_leaf);
+}